@@ -1,15 +1,21 @@
 use anyhow::{Result, anyhow, bail};
+use bstr::io::BufReadExt;
 use clap::Parser;
-use csv::{ReaderBuilder, StringRecord, WriterBuilder};
+use flate2::bufread::MultiGzDecoder;
+use memchr::memchr_iter;
 use regex::Regex;
 use std::{
+    collections::HashMap,
     fs::File,
-    io::{self, BufRead, BufReader},
+    io::{self, BufRead, BufReader, BufWriter, Write},
     num::NonZeroUsize,
     ops::Range,
     process,
 };
 
+/// First two bytes of a gzip stream, per RFC 1952.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
 #[derive(Debug, Parser)]
 #[command(author, version, about)]
 /// Rust version of `cut`
@@ -22,6 +28,11 @@ struct Args {
     #[arg(short, long, value_name = "DELIMITER", default_value = "\t")]
     delimiter: String,
 
+    /// Treat the first record as a header, resolving named --fields
+    /// selectors against it
+    #[arg(short('H'), long)]
+    header: bool,
+
     #[command(flatten)]
     extract: ArgsExtract,
 }
@@ -46,11 +57,19 @@ type PositionList = Vec<Range<usize>>;
 
 #[derive(Debug)]
 enum Extract {
-    Fields(PositionList),
+    Fields(Vec<FieldSelector>),
     Bytes(PositionList),
     Chars(PositionList),
 }
 
+/// A single `--fields` selector: either a 1-based numeric position/range, or
+/// (with `--header`) a column name resolved against the header record.
+#[derive(Debug, Clone)]
+enum FieldSelector {
+    Position(Range<usize>),
+    Name(String),
+}
+
 fn main() {
     if let Err(e) = run(Args::parse()) {
         eprintln!("{}", e);
@@ -65,7 +84,13 @@ fn run(args: Args) -> Result<()> {
     }
     let delimiter = *delim_bytes.first().unwrap();
 
-    let extract = if let Some(fields) = args.extract.fields.map(parse_pos).transpose()? {
+    let extract = if let Some(fields) = args
+        .extract
+        .fields
+        .as_deref()
+        .map(|raw| parse_fields(raw, args.header))
+        .transpose()?
+    {
         Extract::Fields(fields)
     } else if let Some(bytes) = args.extract.bytes.map(parse_pos).transpose()? {
         Extract::Bytes(bytes)
@@ -75,45 +100,77 @@ fn run(args: Args) -> Result<()> {
         unreachable!("Must have --fields, --bytes, or --chars");
     };
 
+    let mut out = BufWriter::new(io::stdout());
+
     for filename in &args.files {
         match open(filename) {
             Err(err) => eprint!("{filename}: {err}"),
-            Ok(file) => match &extract {
-                Extract::Fields(field_pos) => {
-                    let mut reader = ReaderBuilder::new()
-                        .delimiter(delimiter)
-                        .has_headers(false)
-                        .from_reader(file);
-
-                    let mut wtr = WriterBuilder::new()
-                        .delimiter(delimiter)
-                        .from_writer(io::stdout());
-
-                    for record in reader.records() {
-                        wtr.write_record(extract_fields(&record?, field_pos))?;
-                    }
+            Ok(mut file) => match &extract {
+                Extract::Fields(selectors) => {
+                    let mut field_pos = if args.header {
+                        None
+                    } else {
+                        Some(resolve_selectors(selectors, &HashMap::new())?)
+                    };
+                    file.for_byte_line_with_terminator(|line| {
+                        let line = strip_terminator(line);
+                        if field_pos.is_none() {
+                            let header = header_index(line, delimiter);
+                            field_pos = Some(
+                                resolve_selectors(selectors, &header)
+                                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+                            );
+                        }
+                        write_fields(&mut out, line, delimiter, field_pos.as_ref().unwrap())?;
+                        Ok(true)
+                    })?;
                 }
                 Extract::Bytes(byte_pos) => {
-                    for line in file.lines() {
-                        println!("{}", extract_bytes(&line?, byte_pos));
-                    }
+                    file.for_byte_line_with_terminator(|line| {
+                        writeln!(out, "{}", extract_bytes(strip_terminator(line), byte_pos))?;
+                        Ok(true)
+                    })?;
                 }
                 Extract::Chars(char_pos) => {
-                    for line in file.lines() {
-                        println!("{}", extract_chars(&line?, char_pos));
-                    }
+                    file.for_byte_line_with_terminator(|line| {
+                        writeln!(out, "{}", extract_chars(strip_terminator(line), char_pos))?;
+                        Ok(true)
+                    })?;
                 }
             },
         }
     }
 
+    out.flush()?;
+
     Ok(())
 }
 
+/// Strips a trailing `"\n"` or `"\r\n"` left by
+/// [`BufReadExt::for_byte_line_with_terminator`].
+fn strip_terminator(line: &[u8]) -> &[u8] {
+    line.strip_suffix(b"\n")
+        .map(|l| l.strip_suffix(b"\r").unwrap_or(l))
+        .unwrap_or(line)
+}
+
 fn open(filename: &str) -> Result<Box<dyn BufRead>> {
-    match filename {
-        "-" => Ok(Box::new(BufReader::new(io::stdin()))),
-        _ => Ok(Box::new(BufReader::new(File::open(filename)?))),
+    let reader: Box<dyn BufRead> = match filename {
+        "-" => Box::new(BufReader::new(io::stdin())),
+        _ => Box::new(BufReader::new(File::open(filename)?)),
+    };
+    decompress_if_gzip(reader)
+}
+
+/// Peeks at the first two bytes of `reader` and, if they match the gzip
+/// magic number, wraps it in a `MultiGzDecoder` so concatenated gzip
+/// members are read through in full. Falls back to the raw bytes otherwise.
+fn decompress_if_gzip(mut reader: Box<dyn BufRead>) -> Result<Box<dyn BufRead>> {
+    let is_gzip = reader.fill_buf()?.starts_with(&GZIP_MAGIC);
+    if is_gzip {
+        Ok(Box::new(BufReader::new(MultiGzDecoder::new(reader))))
+    } else {
+        Ok(reader)
     }
 }
 
@@ -155,26 +212,113 @@ fn parse_pos(range: String) -> Result<PositionList> {
         .map_err(From::from)
 }
 
-fn extract_fields<'a>(record: &'a StringRecord, field_pos: &[Range<usize>]) -> Vec<&'a str> {
-    field_pos
+/// Splits `line` on `delimiter` using `memchr`, returning the byte range of
+/// each field (delimiters excluded).
+fn split_fields(line: &[u8], delimiter: u8) -> Vec<Range<usize>> {
+    let mut fields = Vec::new();
+    let mut start = 0;
+    for pos in memchr_iter(delimiter, line) {
+        fields.push(start..pos);
+        start = pos + 1;
+    }
+    fields.push(start..line.len());
+    fields
+}
+
+/// Writes the selected fields of `line` straight to `out`, re-joined with
+/// `delimiter`, copying only the bytes that are actually selected.
+fn write_fields<W: Write>(
+    out: &mut W,
+    line: &[u8],
+    delimiter: u8,
+    field_pos: &[Range<usize>],
+) -> io::Result<()> {
+    let fields = split_fields(line, delimiter);
+    let mut need_delim = false;
+    for range in field_pos.iter().cloned() {
+        for field in range.filter_map(|i| fields.get(i)) {
+            if need_delim {
+                out.write_all(&[delimiter])?;
+            }
+            out.write_all(&line[field.clone()])?;
+            need_delim = true;
+        }
+    }
+    out.write_all(b"\n")
+}
+
+/// Parses a `--fields` argument into a list of selectors. Numeric positions
+/// and ranges are always accepted; a bare name is only accepted when
+/// `header` is set, since it must be resolved against the header record.
+fn parse_fields(raw: &str, header: bool) -> Result<Vec<FieldSelector>> {
+    let range_re = Regex::new(r"^(\d+)-(\d+)$").unwrap();
+    raw.split(',')
+        .map(|val| {
+            if let Ok(n) = parse_index(val) {
+                return Ok(FieldSelector::Position(n..n + 1));
+            }
+            if let Some(captures) = range_re.captures(val) {
+                let n1 = parse_index(&captures[1])?;
+                let n2 = parse_index(&captures[2])?;
+                if n1 > n2 {
+                    bail!(
+                        "First number in range ({}) \
+                        must be lower than second number ({})",
+                        n1 + 1,
+                        n2 + 1,
+                    );
+                }
+                return Ok(FieldSelector::Position(n1..n2 + 1));
+            }
+            if header {
+                Ok(FieldSelector::Name(val.to_string()))
+            } else {
+                Err(anyhow!(r#"illegal list value: "{val}""#))
+            }
+        })
+        .collect()
+}
+
+/// Builds a column name -> index map from a header record, splitting on
+/// `delimiter` the same way data rows are split.
+fn header_index(line: &[u8], delimiter: u8) -> HashMap<String, usize> {
+    split_fields(line, delimiter)
+        .into_iter()
+        .enumerate()
+        .map(|(i, range)| (String::from_utf8_lossy(&line[range]).into_owned(), i))
+        .collect()
+}
+
+/// Translates each selector into a concrete field range, resolving names
+/// against `header`.
+fn resolve_selectors(
+    selectors: &[FieldSelector],
+    header: &HashMap<String, usize>,
+) -> Result<PositionList> {
+    selectors
         .iter()
-        .cloned()
-        .flat_map(|range| range.filter_map(|i| record.get(i)))
+        .map(|selector| match selector {
+            FieldSelector::Position(range) => Ok(range.clone()),
+            FieldSelector::Name(name) => header
+                .get(name)
+                .map(|&i| i..i + 1)
+                .ok_or_else(|| anyhow!(r#"column "{name}" not found in header"#)),
+        })
         .collect()
 }
 
-fn extract_bytes(line: &str, byte_pos: &[Range<usize>]) -> String {
-    let bytes = line.as_bytes();
+fn extract_bytes(line: &[u8], byte_pos: &[Range<usize>]) -> String {
     let selected: Vec<_> = byte_pos
         .iter()
         .cloned()
-        .flat_map(|range| range.filter_map(|i| bytes.get(i)).copied())
+        .flat_map(|range| range.filter_map(|i| line.get(i)).copied())
         .collect();
     String::from_utf8_lossy(&selected).into_owned()
 }
 
-fn extract_chars(line: &str, char_pos: &[Range<usize>]) -> String {
-    let chars: Vec<_> = line.chars().collect();
+fn extract_chars(line: &[u8], char_pos: &[Range<usize>]) -> String {
+    let text = String::from_utf8_lossy(line);
+    let chars: Vec<_> = text.chars().collect();
     char_pos
         .iter()
         .cloned()
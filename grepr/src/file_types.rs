@@ -0,0 +1,28 @@
+//! Maps `--type` names to the glob patterns they expand to.
+
+/// Known `--type` names and the globs each one expands to. New mappings can
+/// be added here without touching the file-discovery walker.
+const TYPE_TABLE: &[(&str, &[&str])] = &[
+    ("rust", &["*.rs"]),
+    ("py", &["*.py"]),
+    ("python", &["*.py"]),
+    ("js", &["*.js", "*.mjs", "*.cjs"]),
+    ("ts", &["*.ts", "*.tsx"]),
+    ("go", &["*.go"]),
+    ("c", &["*.c", "*.h"]),
+    ("cpp", &["*.cpp", "*.cc", "*.cxx", "*.hpp", "*.hh"]),
+    ("java", &["*.java"]),
+    ("md", &["*.md", "*.markdown"]),
+    ("toml", &["*.toml"]),
+    ("json", &["*.json"]),
+    ("yaml", &["*.yml", "*.yaml"]),
+    ("sh", &["*.sh", "*.bash"]),
+];
+
+/// Returns the glob patterns registered for `name`, if any.
+pub fn globs_for_type(name: &str) -> Option<&'static [&'static str]> {
+    TYPE_TABLE
+        .iter()
+        .find(|(type_name, _)| *type_name == name)
+        .map(|(_, globs)| *globs)
+}
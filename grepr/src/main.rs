@@ -1,13 +1,23 @@
 use anyhow::{Result, anyhow};
 use clap::Parser;
+use file_types::globs_for_type;
+use flate2::bufread::MultiGzDecoder;
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use regex::{Regex, RegexBuilder};
 use std::{
+    collections::VecDeque,
     fs::{self, File},
     io::{self, BufRead, BufReader},
     mem,
+    path::Path,
 };
 use walkdir::WalkDir;
 
+mod file_types;
+
+/// First two bytes of a gzip stream, per RFC 1952.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
 #[derive(Debug, Parser)]
 #[command(author, version, about)]
 /// Rust version of `grep`
@@ -35,6 +45,27 @@ struct Args {
     /// Invert match
     #[arg(short('v'), long("invert-match"))]
     invert: bool,
+
+    /// Lines of context to print after each match
+    #[arg(short('A'), long("after-context"), value_name = "NUM")]
+    after_context: Option<usize>,
+
+    /// Lines of context to print before each match
+    #[arg(short('B'), long("before-context"), value_name = "NUM")]
+    before_context: Option<usize>,
+
+    /// Lines of context to print before and after each match
+    #[arg(short('C'), long, value_name = "NUM")]
+    context: Option<usize>,
+
+    /// Only search files matching this glob (prefix with `!` to exclude);
+    /// may be repeated
+    #[arg(short('g'), long("glob"), value_name = "GLOB")]
+    globs: Vec<String>,
+
+    /// Only search files of this known type (e.g. rust, py); may be repeated
+    #[arg(short('t'), long("type"), value_name = "TYPE")]
+    file_type: Vec<String>,
 }
 
 fn main() {
@@ -44,13 +75,27 @@ fn main() {
     }
 }
 
+/// Separator emitted between non-adjacent context groups, mirroring
+/// ripgrep's `--` convention. Printed without a filename prefix.
+const GROUP_SEPARATOR: &str = "--\n";
+
 fn run(args: Args) -> Result<()> {
     let pattern = RegexBuilder::new(&args.pattern)
         .case_insensitive(args.insensitive)
         .build()
         .map_err(|_| anyhow!(r#"Invalid pattern "{}""#, args.pattern))?;
 
-    let entries = find_files(&args.files, args.recursive);
+    let (before, after) = match args.context {
+        Some(n) => (n, n),
+        None => (
+            args.before_context.unwrap_or(0),
+            args.after_context.unwrap_or(0),
+        ),
+    };
+
+    let filter = GlobFilter::build(&args.globs, &args.file_type)?;
+
+    let entries = find_files(&args.files, args.recursive, &filter);
     let num_files = entries.len();
     let print = |fname: &str, val: &str| {
         if num_files > 1 {
@@ -65,14 +110,18 @@ fn run(args: Args) -> Result<()> {
             Err(e) => eprintln!("{e}"),
             Ok(filename) => match open(&filename) {
                 Err(e) => eprintln!("{filename}: {e}"),
-                Ok(file) => match find_lines(file, &pattern, args.invert) {
+                Ok(file) => match find_lines(file, &pattern, args.invert, before, after) {
                     Err(e) => eprintln!("{e}"),
-                    Ok(matches) => {
+                    Ok((count, output)) => {
                         if args.count {
-                            print(&filename, &format!("{}\n", matches.len()));
+                            print(&filename, &format!("{count}\n"));
                         } else {
-                            for line in &matches {
-                                print(&filename, line);
+                            for line in &output {
+                                if line == GROUP_SEPARATOR {
+                                    println!("--");
+                                } else {
+                                    print(&filename, line);
+                                }
                             }
                         }
                     }
@@ -85,14 +134,42 @@ fn run(args: Args) -> Result<()> {
 }
 
 fn open(filename: &str) -> Result<Box<dyn BufRead>> {
-    match filename {
-        "-" => Ok(Box::new(BufReader::new(io::stdin()))),
-        _ => Ok(Box::new(BufReader::new(File::open(filename)?))),
+    let reader: Box<dyn BufRead> = match filename {
+        "-" => Box::new(BufReader::new(io::stdin())),
+        _ => Box::new(BufReader::new(File::open(filename)?)),
+    };
+    decompress_if_gzip(reader)
+}
+
+/// Peeks at the first two bytes of `reader` and, if they match the gzip
+/// magic number, wraps it in a `MultiGzDecoder` so concatenated gzip
+/// members are read through in full. Falls back to the raw bytes otherwise.
+fn decompress_if_gzip(mut reader: Box<dyn BufRead>) -> Result<Box<dyn BufRead>> {
+    let is_gzip = reader.fill_buf()?.starts_with(&GZIP_MAGIC);
+    if is_gzip {
+        Ok(Box::new(BufReader::new(MultiGzDecoder::new(reader))))
+    } else {
+        Ok(reader)
     }
 }
 
-fn find_lines<T: BufRead>(mut file: T, pattern: &Regex, invert: bool) -> Result<Vec<String>> {
-    let mut matches = vec![];
+/// Scans `file` for lines matching `pattern`, streaming line numbers so
+/// that `before`/`after` lines of context can be attached to each match.
+/// Returns the number of matches and the lines to print (matches plus any
+/// surrounding context, with a `--` separator between non-adjacent groups).
+fn find_lines<T: BufRead>(
+    mut file: T,
+    pattern: &Regex,
+    invert: bool,
+    before: usize,
+    after: usize,
+) -> Result<(usize, Vec<String>)> {
+    let mut count = 0;
+    let mut output = vec![];
+    let mut before_buf: VecDeque<String> = VecDeque::with_capacity(before);
+    let mut after_remaining = 0;
+    let mut last_printed: Option<usize> = None;
+    let mut line_num = 0;
     let mut line = String::new();
 
     loop {
@@ -100,17 +177,40 @@ fn find_lines<T: BufRead>(mut file: T, pattern: &Regex, invert: bool) -> Result<
         if bytes == 0 {
             break;
         }
+        line_num += 1;
 
         if pattern.is_match(&line) ^ invert {
-            matches.push(mem::take(&mut line));
+            count += 1;
+
+            let first_buffered = line_num - before_buf.len();
+            if let Some(last) = last_printed {
+                if (before > 0 || after > 0) && first_buffered > last + 1 {
+                    output.push(GROUP_SEPARATOR.to_string());
+                }
+            }
+
+            output.extend(before_buf.drain(..));
+            output.push(mem::take(&mut line));
+            last_printed = Some(line_num);
+            after_remaining = after;
+        } else if after_remaining > 0 {
+            output.push(mem::take(&mut line));
+            last_printed = Some(line_num);
+            after_remaining -= 1;
+        } else if before > 0 {
+            if before_buf.len() == before {
+                before_buf.pop_front();
+            }
+            before_buf.push_back(mem::take(&mut line));
         }
+
         line.clear();
     }
 
-    Ok(matches)
+    Ok((count, output))
 }
 
-fn find_files(paths: &[String], recursive: bool) -> Vec<Result<String>> {
+fn find_files(paths: &[String], recursive: bool, filter: &GlobFilter) -> Vec<Result<String>> {
     let mut results = vec![];
 
     for path in paths {
@@ -125,6 +225,7 @@ fn find_files(paths: &[String], recursive: bool) -> Vec<Result<String>> {
                                 .into_iter()
                                 .flatten()
                                 .filter(|f| f.file_type().is_file())
+                                .filter(|f| filter.matches(f.path()))
                             {
                                 results.push(Ok(entry.path().display().to_string()));
                             }
@@ -142,3 +243,53 @@ fn find_files(paths: &[String], recursive: bool) -> Vec<Result<String>> {
 
     results
 }
+
+/// Compiled `--glob`/`--type` filters applied to files discovered by a
+/// recursive walk. A path is kept when it matches the include set (or there
+/// isn't one) and doesn't match the exclude set (patterns prefixed `!`).
+struct GlobFilter {
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl GlobFilter {
+    fn build(globs: &[String], types: &[String]) -> Result<Self> {
+        let mut include = GlobSetBuilder::new();
+        let mut exclude = GlobSetBuilder::new();
+
+        let mut add_pattern = |pattern: &str| -> Result<()> {
+            let (negate, pat) = match pattern.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, pattern),
+            };
+            let glob =
+                Glob::new(pat).map_err(|e| anyhow!(r#"invalid glob "{pattern}": {e}"#))?;
+            if negate {
+                exclude.add(glob);
+            } else {
+                include.add(glob);
+            }
+            Ok(())
+        };
+
+        for pattern in globs {
+            add_pattern(pattern)?;
+        }
+        for name in types {
+            let patterns =
+                globs_for_type(name).ok_or_else(|| anyhow!(r#"unrecognized --type "{name}""#))?;
+            for pattern in patterns {
+                add_pattern(pattern)?;
+            }
+        }
+
+        Ok(GlobFilter {
+            include: include.build()?,
+            exclude: exclude.build()?,
+        })
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        (self.include.is_empty() || self.include.is_match(path)) && !self.exclude.is_match(path)
+    }
+}
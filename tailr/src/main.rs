@@ -1,13 +1,22 @@
 use crate::TakeValue::*;
 use anyhow::{Result, anyhow, bail};
 use clap::Parser;
+use flate2::bufread::MultiGzDecoder;
 use regex::Regex;
 use std::sync::OnceLock;
+use std::time::Duration;
 use std::{
-    fs::File,
-    io::{BufRead, BufReader, Read, Seek, SeekFrom},
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Cursor, Read, Seek, SeekFrom, Write},
+    thread,
 };
 
+/// How long to sleep between polls in `--follow` mode.
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// First two bytes of a gzip stream, per RFC 1952.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
 #[derive(Debug, Parser)]
 #[command(author, version, about)]
 /// Rust version of `tail`
@@ -27,6 +36,10 @@ struct Args {
     /// Suppress headers
     #[arg(short, long)]
     quiet: bool,
+
+    /// Print appended data as the file grows
+    #[arg(short, long)]
+    follow: bool,
 }
 
 static NUM_RE: OnceLock<Regex> = OnceLock::new();
@@ -54,28 +67,124 @@ fn run(args: Args) -> Result<()> {
         .map_err(|e| anyhow!("illegal byte count -- {e}"))?;
 
     let num_files = args.files.len();
+    let mut offsets = vec![0u64; num_files];
+    let mut is_gzip = vec![false; num_files];
     for (file_num, filename) in args.files.iter().enumerate() {
         match File::open(filename) {
             Err(e) => eprintln!("{filename}: {e}"),
-            Ok(file) => {
+            Ok(_) => {
                 if !args.quiet && num_files > 1 {
                     println!("{}==> {filename} <==", if file_num > 0 { "\n" } else { "" },);
                 }
 
                 let (total_lines, total_bytes) = count_line_bytes(filename)?;
-                let file = BufReader::new(file);
                 if let Some(num_bytes) = &bytes {
-                    print_bytes(file, num_bytes, total_bytes)?;
+                    print_bytes(open_seekable(filename)?, num_bytes, total_bytes)?;
                 } else {
-                    print_lines(file, &lines, total_lines)?;
+                    print_lines(open(filename)?, &lines, total_lines)?;
                 }
+                offsets[file_num] = fs::metadata(filename)?.len();
+                is_gzip[file_num] = is_gzip_file(filename)?;
+            }
+        }
+    }
+
+    if args.follow {
+        for (filename, &gzip) in args.files.iter().zip(&is_gzip) {
+            if gzip {
+                eprintln!("{filename}: cannot follow a gzip-compressed file, skipping");
             }
         }
+        follow(&args.files, offsets, is_gzip, args.quiet)?;
     }
 
     Ok(())
 }
 
+/// Polls each file for growth, printing newly appended bytes as they
+/// arrive. `offsets` holds the byte position already printed for each file,
+/// reused from the initial tail. `is_gzip` marks files that were
+/// transparently decompressed for the initial tail; following reads raw
+/// bytes, so those files are skipped rather than followed as nonsense.
+/// Runs until the process is killed, like the classic `tail -f`.
+fn follow(files: &[String], mut offsets: Vec<u64>, is_gzip: Vec<bool>, quiet: bool) -> Result<()> {
+    let num_files = files.len();
+    let mut last_printed: Option<usize> = None;
+
+    loop {
+        for (file_num, filename) in files.iter().enumerate() {
+            if is_gzip[file_num] {
+                continue;
+            }
+
+            let Ok(metadata) = fs::metadata(filename) else {
+                continue;
+            };
+            let len = metadata.len();
+            if len < offsets[file_num] {
+                offsets[file_num] = 0;
+            }
+
+            if len > offsets[file_num] {
+                let mut file = File::open(filename)?;
+                file.seek(SeekFrom::Start(offsets[file_num]))?;
+                let mut buf = Vec::new();
+                file.read_to_end(&mut buf)?;
+
+                if !quiet && num_files > 1 && last_printed != Some(file_num) {
+                    println!("==> {filename} <==");
+                }
+                print!("{}", String::from_utf8_lossy(&buf));
+                io::stdout().flush()?;
+
+                offsets[file_num] = len;
+                last_printed = Some(file_num);
+            }
+        }
+
+        thread::sleep(FOLLOW_POLL_INTERVAL);
+    }
+}
+
+/// Reports whether `filename` starts with the gzip magic number.
+fn is_gzip_file(filename: &str) -> Result<bool> {
+    let mut file = BufReader::new(File::open(filename)?);
+    Ok(file.fill_buf()?.starts_with(&GZIP_MAGIC))
+}
+
+/// Opens `filename`, transparently decompressing it if its first two bytes
+/// carry the gzip magic number (so a `MultiGzDecoder` reads through any
+/// concatenated gzip members). Falls back to the raw bytes otherwise.
+fn open(filename: &str) -> Result<Box<dyn BufRead>> {
+    let mut file = BufReader::new(File::open(filename)?);
+    let is_gzip = file.fill_buf()?.starts_with(&GZIP_MAGIC);
+    if is_gzip {
+        Ok(Box::new(BufReader::new(MultiGzDecoder::new(file))))
+    } else {
+        Ok(Box::new(file))
+    }
+}
+
+/// A reader that supports both `Read` and `Seek`, so `print_bytes` can jump
+/// straight to its start offset.
+trait ReadSeek: Read + Seek {}
+impl<T: Read + Seek> ReadSeek for T {}
+
+/// Like [`open`], but returns a seekable reader. Gzip input can't be seeked
+/// in its compressed form, so it's decompressed fully into memory and
+/// handed back as a `Cursor`; plain files are seeked directly.
+fn open_seekable(filename: &str) -> Result<Box<dyn ReadSeek>> {
+    let mut file = BufReader::new(File::open(filename)?);
+    let is_gzip = file.fill_buf()?.starts_with(&GZIP_MAGIC);
+    if is_gzip {
+        let mut buf = Vec::new();
+        MultiGzDecoder::new(file).read_to_end(&mut buf)?;
+        Ok(Box::new(Cursor::new(buf)))
+    } else {
+        Ok(Box::new(file))
+    }
+}
+
 fn parse_num(val: String) -> Result<TakeValue> {
     let num_re = NUM_RE.get_or_init(|| Regex::new(r"^([+-])?(\d+)$").unwrap());
 
@@ -99,7 +208,7 @@ fn parse_num(val: String) -> Result<TakeValue> {
 }
 
 fn count_line_bytes(filename: &str) -> Result<(i64, i64)> {
-    let mut file = BufReader::new(File::open(filename)?);
+    let mut file = open(filename)?;
     let mut num_lines = 0;
     let mut num_bytes = 0;
     let mut buf = Vec::new();